@@ -0,0 +1,84 @@
+use super::oci;
+
+/// Session mode passed to `OCISessionBegin`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionMode {
+    Default,
+    Sysdba,
+    Sysoper,
+}
+
+impl SessionMode {
+    pub(crate) fn as_oci(self) -> u32 {
+        match self {
+            SessionMode::Default => oci::OCI_DEFAULT_MODE,
+            SessionMode::Sysdba => oci::OCI_SYSDBA,
+            SessionMode::Sysoper => oci::OCI_SYSOPER,
+        }
+    }
+}
+
+/// Credential type passed to `OCISessionBegin` by `connect()`. `ConnectionPool`
+/// does not pass it to `OCISessionGet`; there it only decides whether
+/// username/password are set on the `OCIAuthInfo` handle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CredentialType {
+    /// Username/password authentication (`OCI_CRED_RDBMS`).
+    Rdbms,
+    /// OS/external authentication (`OCI_CRED_EXT`); username/password are
+    /// not set on the `OCIAuthInfo` handle (`ConnectionPool` still passes
+    /// them to `OCISessionPoolCreate`).
+    External,
+}
+
+impl CredentialType {
+    pub(crate) fn as_oci(self) -> u32 {
+        match self {
+            CredentialType::Rdbms => oci::OCI_CRED_RDBMS,
+            CredentialType::External => oci::OCI_CRED_EXT,
+        }
+    }
+}
+
+/// Builder for the session mode, credential type, and optional proxy user
+/// used to authenticate a `connect()`. Defaults to `OCI_DEFAULT` mode with
+/// `OCI_CRED_RDBMS` and no proxy user.
+#[derive(Debug, Clone)]
+pub struct ConnectParams {
+    pub(crate) mode: SessionMode,
+    pub(crate) credential: CredentialType,
+    pub(crate) proxy_user: Option<String>,
+}
+
+impl Default for ConnectParams {
+    fn default() -> Self {
+        ConnectParams { mode: SessionMode::Default, credential: CredentialType::Rdbms, proxy_user: None }
+    }
+}
+
+impl ConnectParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Select `OCI_SYSDBA`/`OCI_SYSOPER` instead of the default session mode.
+    pub fn mode(mut self, mode: SessionMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Select `OCI_CRED_EXT` for external/OS authentication instead of the
+    /// default `OCI_CRED_RDBMS`.
+    pub fn credential(mut self, credential: CredentialType) -> Self {
+        self.credential = credential;
+        self
+    }
+
+    /// Connect as `proxy_user` on behalf of the account named in `connect()`,
+    /// i.e. proxy authentication (`alice[bob]` connects as `bob` through
+    /// `alice`'s proxy privilege).
+    pub fn proxy_user(mut self, proxy_user: impl Into<String>) -> Self {
+        self.proxy_user = Some(proxy_user.into());
+        self
+    }
+}
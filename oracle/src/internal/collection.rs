@@ -0,0 +1,64 @@
+//! Bind arrays for `IN (...)` predicates via Oracle collection types.
+//!
+//! Binding a `&[i64]`/`&[String]` to a single named placeholder lets callers
+//! write `... WHERE col IN (SELECT column_value FROM TABLE(:ids))` instead
+//! of splicing N placeholders together. A `BindArray` materializes the slice
+//! as an `OCIColl` instance of `SYS.ODCINUMBERLIST`/`SYS.ODCIVARCHAR2LIST`
+//! (`OCICollAppend` per element) and binds it with `OCIBindByName` against
+//! `SQLT_NTY`.
+
+use std::marker::PhantomData;
+
+use super::connection::Connection;
+use super::oci;
+
+const ODCI_SCHEMA: &str = "SYS";
+const ODCI_NUMBER_LIST: &str = "ODCINUMBERLIST";
+const ODCI_VARCHAR2_LIST: &str = "ODCIVARCHAR2LIST";
+
+/// A slice materialized as an Oracle collection, ready to bind to a named
+/// placeholder with `SQLT_NTY`. Borrows the `Connection` it was built
+/// against for its whole lifetime: the underlying `OCIColl` instance is only
+/// valid for that session, and `Drop` frees it through the same handles.
+pub struct BindArray<'conn> {
+    tdo: *mut oci::OCIType,
+    collp: *mut oci::OCIColl,
+    envhp: *mut oci::OCIEnv,
+    errhp: *mut oci::OCIError,
+    _conn: PhantomData<&'conn Connection>,
+}
+
+impl<'conn> BindArray<'conn> {
+    /// Materialize `values` as a `SYS.ODCINUMBERLIST` collection.
+    pub fn numbers(conn: &'conn Connection, values: &[i64]) -> Result<BindArray<'conn>, oci::OracleError> {
+        let tdo = oci::type_by_name(conn.envhp(), conn.svchp, conn.errhp, ODCI_SCHEMA, ODCI_NUMBER_LIST)?;
+        let collp = oci::new_collection(conn.envhp(), conn.svchp, conn.errhp, tdo)?;
+        for &value in values {
+            oci::coll_append_number(conn.envhp(), conn.errhp, collp, value)?;
+        }
+        Ok(BindArray { tdo, collp, envhp: conn.envhp(), errhp: conn.errhp, _conn: PhantomData })
+    }
+
+    /// Materialize `values` as a `SYS.ODCIVARCHAR2LIST` collection.
+    pub fn strings(conn: &'conn Connection, values: &[impl AsRef<str>]) -> Result<BindArray<'conn>, oci::OracleError> {
+        let tdo = oci::type_by_name(conn.envhp(), conn.svchp, conn.errhp, ODCI_SCHEMA, ODCI_VARCHAR2_LIST)?;
+        let collp = oci::new_collection(conn.envhp(), conn.svchp, conn.errhp, tdo)?;
+        for value in values {
+            oci::coll_append_string(conn.envhp(), conn.errhp, collp, value.as_ref())?;
+        }
+        Ok(BindArray { tdo, collp, envhp: conn.envhp(), errhp: conn.errhp, _conn: PhantomData })
+    }
+
+    /// Bind this collection to `placeholder` (e.g. `":ids"`) on a prepared
+    /// statement, for use as `... FROM TABLE(:ids)`. Called by
+    /// `statement::Query::bind_array`.
+    pub(crate) fn bind(&self, stmthp: *mut oci::OCIStmt, errhp: *mut oci::OCIError, placeholder: &str) -> Result<(), oci::OracleError> {
+        oci::bind_collection_by_name(stmthp, errhp, placeholder, self.tdo, self.collp)
+    }
+}
+
+impl<'conn> Drop for BindArray<'conn> {
+    fn drop(&mut self) {
+        oci::object_free(self.envhp, self.errhp, self.collp as *mut oci::c_void);
+    }
+}
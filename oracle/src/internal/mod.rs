@@ -0,0 +1,17 @@
+mod oci;
+mod auth;
+mod values;
+mod statement;
+mod connection;
+mod pool;
+mod collection;
+#[cfg(feature = "nonblocking")]
+mod nonblocking;
+
+pub use connection::{connect, connect_url, connect_with_params, connect_with_stmt_cache, Connection};
+pub use auth::{ConnectParams, SessionMode, CredentialType};
+pub use pool::ConnectionPool;
+pub use collection::BindArray;
+pub use statement::Query;
+pub use values::{DescriptorsProvider, FromResultSet};
+pub use oci::{Diagnostic, OracleError, OracleResult};
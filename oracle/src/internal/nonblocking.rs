@@ -0,0 +1,56 @@
+//! Non-blocking execution, gated behind the `nonblocking` cargo feature.
+//!
+//! Built on OCI's `OCI_ATTR_NONBLOCKING_MODE` (set on the server handle by
+//! `connect_full` under this feature) and the `OCI_STILL_EXECUTING` return
+//! path `check_error` already special-cases: a call is issued, and while OCI
+//! reports it's still executing, the task yields to the runtime instead of
+//! busy-spinning. This requires the `Environment` to have been created with
+//! `OCI_THREADED` (see `Environment::new`).
+//!
+//! Partial delivery: only `commit_async`/`rollback_async` are awaitable so
+//! far. `make_query`/`statement::Query` still go through the blocking path,
+//! so the query path itself isn't non-blocking yet (see the `TODO` below).
+
+use super::connection::Connection;
+use super::oci;
+
+/// Drive a blocking OCI call to completion without busy-spinning, yielding
+/// to the async runtime on every `OCI_STILL_EXECUTING`.
+async fn drive<F>(mut call: F) -> Result<(), oci::OracleError>
+where
+    F: FnMut() -> Result<(), oci::OracleError>,
+{
+    loop {
+        match call() {
+            Err(ref err) if oci::is_still_executing(err) => yield_now().await,
+            other => return other,
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+async fn yield_now() {
+    tokio::task::yield_now().await;
+}
+
+#[cfg(all(feature = "actix", not(feature = "tokio")))]
+async fn yield_now() {
+    actix_rt::task::yield_now().await;
+}
+
+impl Connection {
+    /// Non-blocking variant of `commit`.
+    pub async fn commit_async(&self) -> Result<(), oci::OracleError> {
+        drive(|| self.commit()).await
+    }
+
+    /// Non-blocking variant of `rollback`.
+    pub async fn rollback_async(&self) -> Result<(), oci::OracleError> {
+        drive(|| self.rollback()).await
+    }
+
+    // TODO: `make_query` should route its prepare OCI call through `drive`
+    // the same way once the
+    // statement module grows an async entry point, so the whole query path
+    // is awaitable rather than just commit/rollback.
+}
@@ -2,57 +2,577 @@ use std::{
     error, fmt, ptr
 };
 
-/// Represents Oracle error
+/// A single record from OCI's diagnostic stack: the `(errcode, sqlstate,
+/// message)` tuple `OCIErrorGet` returns for one `recordno`.
 #[derive(Debug, Clone)]
-pub struct OracleError {
-    /// Oracle error code
+pub struct Diagnostic {
     pub errcode: i32,
-    /// Message from Oracle
-    message:     String,
-    // Function where error occured
-    location:    &'static str
+    /// The 5-character SQLSTATE, when OCI populated one for this record.
+    pub sqlstate: Option<String>,
+    pub message: String,
+}
+
+/// Represents an Oracle error
+#[derive(Debug, Clone)]
+pub enum OracleError {
+    /// An error reported by Oracle through the OCI error handle: the full
+    /// diagnostic chain (`OCIErrorGet` recordno 1, 2, … until `OCI_NO_DATA`)
+    /// and the SQLSTATE of its first record.
+    Oracle {
+        sqlstate: Option<String>,
+        records: Vec<Diagnostic>,
+        location: &'static str,
+    },
+    /// An internal/interface error that never reached Oracle's error stack:
+    /// bad UTF-8, a nul byte in a bind value, and the like.
+    Internal(String),
 }
 
 pub type OracleResult<T> = Result<T, OracleError>;
 
 impl OracleError {
+    /// Build an internal/interface error that never reached Oracle's error
+    /// stack (invalid UTF-8, a nul byte in a bind value, and the like).
     pub fn new(message: String, location: &'static str) -> OracleError {
-        OracleError { errcode: 200, message, location}
+        OracleError::Internal(format!("{} (at {})", message, location))
+    }
+
+    /// The primary Oracle error code, i.e. the first diagnostic record's
+    /// code. `None` for `Internal` errors, which never reached OCI.
+    pub fn errcode(&self) -> Option<i32> {
+        match self {
+            OracleError::Oracle { records, .. } => records.first().map(|d| d.errcode),
+            OracleError::Internal(_) => None,
+        }
     }
 }
 
 impl fmt::Display for OracleError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!{f, "\n\n   Error code: {}\n   Error message: {}\n   Where: {}\n\n",
-                self.errcode, self.message, self.location}
+        match self {
+            OracleError::Oracle { sqlstate, records, location } => {
+                writeln!(f)?;
+                writeln!(f, "   Where: {}", location)?;
+                if let Some(state) = sqlstate {
+                    writeln!(f, "   SQLSTATE: {}", state)?;
+                }
+                for (i, d) in records.iter().enumerate() {
+                    writeln!(f, "   [{}] Error code: {}  Message: {}", i + 1, d.errcode, d.message)?;
+                }
+                Ok(())
+            }
+            OracleError::Internal(message) => write!(f, "\n\n   Internal error: {}\n\n", message),
+        }
     }
 }
 
-impl error::Error for OracleError {
-    fn description(&self) -> &str {
-        self.message.as_str()
+impl error::Error for OracleError {}
+
+const OCI_THREADED: u32 = 0x00000001;
+
+/// True when an OCI call returned `OCI_STILL_EXECUTING`, meaning it must be
+/// retried rather than treated as a terminal error. Used by the
+/// `nonblocking` feature to drive a poll/retry loop.
+#[cfg(feature = "nonblocking")]
+pub(crate) fn is_still_executing(err: &OracleError) -> bool {
+    err.errcode() == Some(OCI_STILL_EXECUTING)
+}
+
+/// Server-handle attribute that switches OCI calls made over it to
+/// non-blocking mode, where a call that would otherwise block instead
+/// returns `OCI_STILL_EXECUTING` immediately and must be retried. Set once
+/// on `srvhp` right after `server_attach` by `connect_full` under the
+/// `nonblocking` feature; without it OCI never returns `OCI_STILL_EXECUTING`
+/// and the `nonblocking::drive` retry loop never actually triggers.
+#[cfg(feature = "nonblocking")]
+pub(crate) const OCI_ATTR_NONBLOCKING_MODE: u32 = 6;
+
+extern "C" {
+    fn OCIEnvCreate(
+        envhpp: *mut *mut OCIEnv,
+        mode: u32,
+        ctxp: *mut c_void,
+        malocfp: *mut c_void,
+        ralocfp: *mut c_void,
+        mfreefp: *mut c_void,
+        xtramemsz: usize,
+        usrmempp: *mut *mut c_void,
+    ) -> i32;
+}
+
+/// Create the environment handle with `OCI_THREADED` set. This is required
+/// unconditionally, not just under the `nonblocking` feature: `ConnectionPool`
+/// hands sessions out for concurrent use from multiple threads, which is
+/// only sound against a threaded environment.
+pub(crate) fn env_create_threaded() -> Result<*mut OCIEnv, OracleError> {
+    let mut envhp: *mut OCIEnv = ptr::null_mut();
+    let errcode = unsafe {
+        OCIEnvCreate(&mut envhp, OCI_THREADED, ptr::null_mut(), ptr::null_mut(),
+                     ptr::null_mut(), ptr::null_mut(), 0, ptr::null_mut())
+    };
+    check_error(errcode, None, "env_create_threaded")?;
+    Ok(envhp)
+}
+
+/// Opaque session pool handle (`OCISPool`)
+pub enum OCISPool {}
+
+pub(crate) const OCI_HTYPE_SPOOL: u32 = 43;
+
+const OCI_SPC_HOMOGENEOUS: u32 = 0x01;
+const OCI_SPC_STMTCACHE:   u32 = 0x02;
+const OCI_SESSGET_SPOOL:   u32 = 0x01;
+
+extern "C" {
+    fn OCISessionPoolCreate(
+        envhp: *mut OCIEnv,
+        errhp: *mut OCIError,
+        poolhp: *mut OCISPool,
+        poolName: *mut *mut u8,
+        poolNameLen: *mut u32,
+        connStr: *const u8,
+        connStrLen: u32,
+        sessMin: u32,
+        sessMax: u32,
+        sessIncr: u32,
+        userid: *const u8,
+        useridLen: u32,
+        password: *const u8,
+        passwordLen: u32,
+        mode: u32,
+    ) -> i32;
+
+    fn OCISessionPoolDestroy(poolhp: *mut OCISPool, errhp: *mut OCIError, mode: u32) -> i32;
+
+    fn OCISessionGet(
+        envhp: *mut OCIEnv,
+        errhp: *mut OCIError,
+        svchp: *mut *mut OCISvcCtx,
+        authinfop: *mut c_void,
+        poolName: *const u8,
+        poolNameLen: u32,
+        tag: *const u8,
+        tagLen: u32,
+        retTag: *mut *mut u8,
+        retTagLen: *mut u32,
+        found: *mut i32,
+        mode: u32,
+    ) -> i32;
+
+    fn OCISessionRelease(svchp: *mut OCISvcCtx, errhp: *mut OCIError, tag: *const u8, tagLen: u32, mode: u32) -> i32;
+}
+
+/// Create a session pool against `db`, sized between `min` and `max`
+/// sessions. Returns the pool name OCI generated, which `session_get` needs
+/// to draw sessions from this pool.
+pub(crate) fn session_pool_create(envhp: *mut OCIEnv,
+                                   poolhp: *mut OCISPool,
+                                   errhp: *mut OCIError,
+                                   db: &str,
+                                   username: &str,
+                                   passwd: &str,
+                                   min: u32,
+                                   max: u32,
+                                   increment: u32) -> Result<String, OracleError> {
+    let mut name_ptr: *mut u8 = ptr::null_mut();
+    let mut name_len: u32 = 0;
+
+    let errcode = unsafe {
+        OCISessionPoolCreate(
+            envhp,
+            errhp,
+            poolhp,
+            &mut name_ptr,
+            &mut name_len,
+            db.as_ptr(),
+            db.len() as u32,
+            min,
+            max,
+            increment,
+            username.as_ptr(),
+            username.len() as u32,
+            passwd.as_ptr(),
+            passwd.len() as u32,
+            OCI_SPC_HOMOGENEOUS | OCI_SPC_STMTCACHE,
+        )
+    };
+    check_error(errcode, Some(errhp), "session_pool_create")?;
+
+    let name = unsafe {
+        std::slice::from_raw_parts(name_ptr, name_len as usize)
+    };
+    Ok(String::from_utf8_lossy(name).into_owned())
+}
+
+/// Destroy a session pool previously created with `session_pool_create`.
+pub(crate) fn session_pool_destroy(poolhp: *mut OCISPool, errhp: *mut OCIError) {
+    let errcode = unsafe { OCISessionPoolDestroy(poolhp, errhp, OCI_DEFAULT) };
+    let _ = check_error(errcode, Some(errhp), "session_pool_destroy");
+}
+
+/// Acquire a session from the named pool via `OCISessionGet`. `authinfop`
+/// (from `prepare_auth_info`) carries the username (in `username[proxy_user]`
+/// form when a proxy user is set) and password, both left unset for
+/// `OCI_CRED_EXT`; the credential type itself is not passed to
+/// `OCISessionGet`. `mode` carries the
+/// session mode (e.g. `OCI_SYSDBA`), OR'd in alongside `OCI_SESSGET_SPOOL`.
+pub(crate) fn session_get(envhp: *mut OCIEnv, errhp: *mut OCIError, pool_name: &str,
+                           authinfop: *mut OCIAuthInfo, mode: u32) -> Result<*mut OCISvcCtx, OracleError> {
+    let mut svchp: *mut OCISvcCtx = ptr::null_mut();
+    let mut found: i32 = 0;
+
+    let errcode = unsafe {
+        OCISessionGet(
+            envhp,
+            errhp,
+            &mut svchp,
+            authinfop as *mut c_void,
+            pool_name.as_ptr(),
+            pool_name.len() as u32,
+            ptr::null(),
+            0,
+            ptr::null_mut(),
+            ptr::null_mut(),
+            &mut found,
+            OCI_SESSGET_SPOOL | mode,
+        )
+    };
+    check_error(errcode, Some(errhp), "session_get")?;
+    Ok(svchp)
+}
+
+/// Release a pooled session back to its pool via `OCISessionRelease`.
+pub(crate) fn session_release(svchp: *mut OCISvcCtx, errhp: *mut OCIError) {
+    let errcode = unsafe { OCISessionRelease(svchp, errhp, ptr::null(), 0, OCI_DEFAULT) };
+    let _ = check_error(errcode, Some(errhp), "session_release");
+}
+
+/// Opaque collection type descriptor (`OCIType`, the TDO for e.g.
+/// `SYS.ODCINUMBERLIST`)
+pub enum OCIType {}
+
+/// Opaque collection instance (`OCIColl`), e.g. an `ODCINUMBERLIST` built up
+/// with `coll_append_number`/`coll_append_string` and bound with `SQLT_NTY`.
+pub enum OCIColl {}
+
+/// Oracle's 22-byte internal number representation.
+type OCINumber = [u8; 22];
+
+pub(crate) const SQLT_NTY: u16 = 108;
+const OCI_DURATION_SESSION: u16 = 10;
+const OCI_TYPEGET_HEADER: u16 = 1;
+
+extern "C" {
+    fn OCITypeByName(
+        envhp: *mut OCIEnv,
+        errhp: *mut OCIError,
+        svchp: *mut OCISvcCtx,
+        schema_name: *const u8,
+        schema_len: u32,
+        type_name: *const u8,
+        type_len: u32,
+        version_name: *const u8,
+        version_len: u32,
+        pin_duration: u16,
+        get_option: u16,
+        tdo: *mut *mut OCIType,
+    ) -> i32;
+
+    fn OCIObjectNew(
+        envhp: *mut OCIEnv,
+        errhp: *mut OCIError,
+        svchp: *mut OCISvcCtx,
+        typecode: u32,
+        tdo: *mut OCIType,
+        table: *mut c_void,
+        duration: u16,
+        value_is_null: u8,
+        instance: *mut *mut OCIColl,
+    ) -> i32;
+
+    fn OCINumberFromInt(errhp: *mut OCIError, inum: *const i64, inum_len: u32, sign: u32, number: *mut OCINumber) -> i32;
+
+    fn OCICollAppend(envhp: *mut OCIEnv, errhp: *mut OCIError, elem: *const c_void, elemind: *const c_void, coll: *mut OCIColl) -> i32;
+
+    fn OCIBindByName(
+        stmthp: *mut OCIStmt,
+        bindpp: *mut *mut c_void,
+        errhp: *mut OCIError,
+        placeholder: *const u8,
+        placeholder_len: i32,
+        valuep: *mut c_void,
+        value_sz: i32,
+        dty: u16,
+        indp: *mut c_void,
+        alenp: *mut u32,
+        rcodep: *mut u16,
+        maxarr_len: u32,
+        curelep: *mut u32,
+        mode: u32,
+    ) -> i32;
+
+    fn OCIBindObject(
+        bindp: *mut c_void,
+        errhp: *mut OCIError,
+        tdo: *const OCIType,
+        pgvpp: *mut *mut c_void,
+        pvetep: *mut u32,
+        indpp: *mut *mut c_void,
+        indszp: *mut u32,
+    ) -> i32;
+}
+
+const OCI_TYPECODE_NAMEDCOLLECTION: u32 = 122;
+
+/// Look up the TDO for a named collection type (e.g. `SYS.ODCINUMBERLIST`),
+/// required before an instance of it can be created with `OCIObjectNew`.
+pub(crate) fn type_by_name(envhp: *mut OCIEnv, svchp: *mut OCISvcCtx, errhp: *mut OCIError,
+                            schema: &str, type_name: &str) -> Result<*mut OCIType, OracleError> {
+    let mut tdo: *mut OCIType = ptr::null_mut();
+    let errcode = unsafe {
+        OCITypeByName(envhp, errhp, svchp,
+                      schema.as_ptr(), schema.len() as u32,
+                      type_name.as_ptr(), type_name.len() as u32,
+                      ptr::null(), 0,
+                      OCI_DURATION_SESSION, OCI_TYPEGET_HEADER, &mut tdo)
+    };
+    check_error(errcode, Some(errhp), "type_by_name")?;
+    Ok(tdo)
+}
+
+/// Create a new, empty instance of the named collection type.
+pub(crate) fn new_collection(envhp: *mut OCIEnv, svchp: *mut OCISvcCtx, errhp: *mut OCIError,
+                              tdo: *mut OCIType) -> Result<*mut OCIColl, OracleError> {
+    let mut collp: *mut OCIColl = ptr::null_mut();
+    let errcode = unsafe {
+        OCIObjectNew(envhp, errhp, svchp, OCI_TYPECODE_NAMEDCOLLECTION, tdo,
+                     ptr::null_mut(), OCI_DURATION_SESSION, 0, &mut collp)
+    };
+    check_error(errcode, Some(errhp), "new_collection")?;
+    Ok(collp)
+}
+
+/// Append an `i64` element to a `SYS.ODCINUMBERLIST`-shaped collection.
+pub(crate) fn coll_append_number(envhp: *mut OCIEnv, errhp: *mut OCIError,
+                                  collp: *mut OCIColl, value: i64) -> Result<(), OracleError> {
+    let mut number: OCINumber = [0u8; 22];
+    let errcode = unsafe {
+        OCINumberFromInt(errhp, &value, std::mem::size_of::<i64>() as u32, 2 /* signed */, &mut number)
+    };
+    check_error(errcode, Some(errhp), "coll_append_number")?;
+
+    let errcode = unsafe {
+        OCICollAppend(envhp, errhp, &number as *const OCINumber as *const c_void, ptr::null(), collp)
+    };
+    check_error(errcode, Some(errhp), "coll_append_number")
+}
+
+/// Opaque Oracle string instance (`OCIString`), used to append `VARCHAR2`
+/// elements to a collection.
+pub enum OCIString {}
+
+extern "C" {
+    fn OCIStringAssignText(env: *mut OCIEnv, errhp: *mut OCIError, rhs: *const u8, rhs_len: u16, lhs: *mut *mut OCIString) -> i32;
+    fn OCIStringResize(env: *mut OCIEnv, errhp: *mut OCIError, new_size: u32, str: *mut *mut OCIString) -> i32;
+    fn OCIObjectFree(envhp: *mut OCIEnv, errhp: *mut OCIError, instance: *mut c_void, flags: u16) -> i32;
+}
+
+/// Append a `&str` element to a `SYS.ODCIVARCHAR2LIST`-shaped collection.
+pub(crate) fn coll_append_string(envhp: *mut OCIEnv, errhp: *mut OCIError,
+                                  collp: *mut OCIColl, value: &str) -> Result<(), OracleError> {
+    let mut ocistr: *mut OCIString = ptr::null_mut();
+    let errcode = unsafe {
+        OCIStringAssignText(envhp, errhp, value.as_ptr(), value.len() as u16, &mut ocistr)
+    };
+    check_error(errcode, Some(errhp), "coll_append_string")?;
+
+    let errcode = unsafe {
+        OCICollAppend(envhp, errhp, &ocistr as *const *mut OCIString as *const c_void, ptr::null(), collp)
+    };
+    check_error(errcode, Some(errhp), "coll_append_string")?;
+
+    // OCICollAppend copies the string's contents into the collection; the
+    // transient OCIString we allocated above must be freed on our own.
+    let errcode = unsafe { OCIStringResize(envhp, errhp, 0, &mut ocistr) };
+    check_error(errcode, Some(errhp), "coll_append_string")
+}
+
+/// Free a collection instance created with `new_collection` (`OCIObjectFree`).
+pub(crate) fn object_free(envhp: *mut OCIEnv, errhp: *mut OCIError, instance: *mut c_void) {
+    let errcode = unsafe { OCIObjectFree(envhp, errhp, instance, 0) };
+    let _ = check_error(errcode, Some(errhp), "object_free");
+}
+
+/// Bind a materialized collection to a named placeholder with `SQLT_NTY`.
+pub(crate) fn bind_collection_by_name(stmthp: *mut OCIStmt, errhp: *mut OCIError,
+                                       placeholder: &str, tdo: *mut OCIType, collp: *mut OCIColl) -> Result<(), OracleError> {
+    let mut bindp: *mut c_void = ptr::null_mut();
+    let errcode = unsafe {
+        OCIBindByName(stmthp, &mut bindp, errhp,
+                      placeholder.as_ptr(), placeholder.len() as i32,
+                      ptr::null_mut(), 0, SQLT_NTY,
+                      ptr::null_mut(), ptr::null_mut(), ptr::null_mut(),
+                      0, ptr::null_mut(), OCI_DEFAULT)
+    };
+    check_error(errcode, Some(errhp), "bind_collection_by_name")?;
+
+    let mut collp = collp;
+    let errcode = unsafe {
+        OCIBindObject(bindp, errhp, tdo, &mut collp as *mut *mut OCIColl as *mut *mut c_void,
+                      ptr::null_mut(), ptr::null_mut(), ptr::null_mut())
+    };
+    check_error(errcode, Some(errhp), "bind_collection_by_name")
+}
+
+/// Opaque authentication-info handle (`OCIAuthInfo`), carrying username and
+/// password. The session mode is not stored on it; it is passed separately
+/// to `session_begin`/`session_get`. Used both for a one-off `connect()` and, via
+/// `OCISessionGet`, for session pooling.
+pub enum OCIAuthInfo {}
+
+pub(crate) const OCI_HTYPE_AUTHINFO: u32 = 42;
+
+pub(crate) const OCI_CRED_RDBMS: u32 = 1;
+pub(crate) const OCI_CRED_EXT: u32 = 2;
+
+pub(crate) const OCI_DEFAULT_MODE: u32 = 0x00000000;
+pub(crate) const OCI_SYSDBA: u32 = 0x00000002;
+pub(crate) const OCI_SYSOPER: u32 = 0x00000004;
+
+const OCI_ATTR_USERNAME: u32 = 22;
+const OCI_ATTR_PASSWORD: u32 = 23;
+
+extern "C" {
+    fn OCISessionBegin(svchp: *mut OCISvcCtx, errhp: *mut OCIError, authp: *mut OCIAuthInfo, credtype: u32, mode: u32) -> i32;
+}
+
+/// Allocate an `OCIAuthInfo` handle and set its username/password, unless
+/// `credential` is `OCI_CRED_EXT` (external auth sends neither). When
+/// `proxy_user` is set, authenticate with `username`'s credentials but
+/// connect as `proxy_user`, using Oracle's `username[proxy_user]` syntax.
+pub(crate) fn prepare_auth_info(envhp: *mut OCIEnv, errhp: *mut OCIError,
+                                 username: &str, passwd: &str,
+                                 credential: u32, proxy_user: Option<&str>) -> Result<*mut OCIAuthInfo, OracleError> {
+    let authp = handle_alloc(envhp, OCI_HTYPE_AUTHINFO)? as *mut OCIAuthInfo;
+
+    if credential != OCI_CRED_EXT {
+        let effective_username = match proxy_user {
+            Some(proxy) => format!("{}[{}]", username, proxy),
+            None => username.to_string(),
+        };
+
+        attr_set(authp as *mut c_void, OCI_HTYPE_AUTHINFO,
+                 effective_username.as_ptr() as *mut c_void, effective_username.len() as u32,
+                 OCI_ATTR_USERNAME, errhp)?;
+        attr_set(authp as *mut c_void, OCI_HTYPE_AUTHINFO,
+                 passwd.as_ptr() as *mut c_void, passwd.len() as u32,
+                 OCI_ATTR_PASSWORD, errhp)?;
     }
+
+    Ok(authp)
+}
+
+/// Begin a session against `svchp` using an `OCIAuthInfo` handle prepared
+/// with `prepare_auth_info`, with the given credential type and session mode.
+pub(crate) fn session_begin(svchp: *mut OCISvcCtx, errhp: *mut OCIError, authp: *mut OCIAuthInfo,
+                             credential: u32, mode: u32) -> Result<(), OracleError> {
+    let errcode = unsafe { OCISessionBegin(svchp, errhp, authp, credential, mode) };
+    check_error(errcode, Some(errhp), "session_begin")
 }
 
-// TODO: create custom OracleError
+/// Opaque prepared-statement handle (`OCIStmt`)
+pub enum OCIStmt {}
 
-/// Returns an error message in the buffer provided and an ORACLE error
+pub(crate) const OCI_ATTR_STMTCACHESIZE: u32 = 176;
+
+const OCI_NTV_SYNTAX: u32 = 1;
+
+extern "C" {
+    fn OCIStmtPrepare2(
+        svchp: *mut OCISvcCtx,
+        stmthp: *mut *mut OCIStmt,
+        errhp: *mut OCIError,
+        stmttext: *const u8,
+        stmt_len: u32,
+        key: *const u8,
+        keylen: u32,
+        language: u32,
+        mode: u32,
+    ) -> i32;
+
+    fn OCIStmtRelease(stmthp: *mut OCIStmt, errhp: *mut OCIError, key: *const u8, keylen: u32, mode: u32) -> i32;
+}
+
+/// Prepare `sql`, served from OCI's own statement cache (enabled via
+/// `OCI_ATTR_STMTCACHESIZE` at connect time) when this text has been seen
+/// before.
+pub(crate) fn stmt_prepare2(svchp: *mut OCISvcCtx, errhp: *mut OCIError, sql: &str) -> Result<*mut OCIStmt, OracleError> {
+    let mut stmthp: *mut OCIStmt = ptr::null_mut();
+    let errcode = unsafe {
+        OCIStmtPrepare2(svchp, &mut stmthp, errhp, sql.as_ptr(), sql.len() as u32,
+                        ptr::null(), 0, OCI_NTV_SYNTAX, OCI_DEFAULT)
+    };
+    check_error(errcode, Some(errhp), "stmt_prepare2")?;
+    Ok(stmthp)
+}
+
+/// Return a statement prepared with `stmt_prepare2` to OCI's statement
+/// cache instead of freeing it outright.
+pub(crate) fn stmt_release(stmthp: *mut OCIStmt, errhp: *mut OCIError) {
+    let errcode = unsafe { OCIStmtRelease(stmthp, errhp, ptr::null(), 0, OCI_DEFAULT) };
+    let _ = check_error(errcode, Some(errhp), "stmt_release");
+}
+
+/// Fetch diagnostic record number `recordno` off `errhp`, or `None` once
+/// `OCIErrorGet` reports `OCI_NO_DATA` (the chain is exhausted).
 #[inline]
-fn error_get(errhp: *mut OCIError, location: &'static str) -> OracleError {
+fn error_get(errhp: *mut OCIError, recordno: u32) -> Option<Diagnostic> {
     let errc: *mut i32 = &mut 0;
-    let mut buf = String::with_capacity(2048);
-    unsafe {
+    let mut buf = vec![0u8; 2048];
+    let mut state = vec![0u8; 16];
+    let res = unsafe {
         OCIErrorGet(
-            errhp as *mut c_void, // hndlp
-            1,                    // recordno
-            ptr::null_mut(),      // sqlstate
-            errc,                 // errcodep
-            buf.as_mut_ptr() as *mut u8,  // bufp
-            buf.capacity() as u32,        // bufsiz
+            errhp as *mut c_void,       // hndlp
+            recordno,                   // recordno
+            state.as_mut_ptr(),         // sqlstate
+            errc,                       // errcodep
+            buf.as_mut_ptr(),           // bufp
+            buf.len() as u32,           // bufsiz
             OCI_HTYPE_ERROR
         )
     };
-    OracleError { errcode: unsafe{ *errc }, message: buf, location }
+    if res == OCI_NO_DATA {
+        return None;
+    }
+
+    let message = cstr_to_string(&buf);
+    let sqlstate = {
+        let s = cstr_to_string(&state[..5]);
+        if s.is_empty() { None } else { Some(s) }
+    };
+
+    Some(Diagnostic { errcode: unsafe { *errc }, sqlstate, message })
+}
+
+/// Trim a nul-terminated OCI output buffer down to a `String`.
+fn cstr_to_string(buf: &[u8]) -> String {
+    let len = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+    String::from_utf8_lossy(&buf[..len]).trim().to_string()
+}
+
+/// Walk the full diagnostic chain off `errhp`, recordno 1, 2, … until
+/// `OCI_NO_DATA`.
+fn diagnostic_chain(errhp: *mut OCIError) -> Vec<Diagnostic> {
+    let mut records = Vec::new();
+    let mut recordno = 1;
+    while let Some(mut d) = error_get(errhp, recordno) {
+        if d.errcode == 24347 {
+            d.message = "NULL column in a aggregate function".to_string();
+        }
+        records.push(d);
+        recordno += 1;
+    }
+    records
 }
 
 /// check errcode for Oracle Error
@@ -60,39 +580,26 @@ pub fn check_error(errcode: i32,
                    handle: Option<*mut OCIError>,
                    location: &'static str) -> Result<(), OracleError> {
     if errcode == OCI_SUCCESS {
-        Ok(())
-    } else {
-        let by_handle =
-            handle.map(|errhp| {
-                let mut error = error_get(errhp, location);
-                if error.errcode == 24347 {
-                    error.message = "NULL column in a aggregate function".to_string();
-                }
-                error
-            });
-
-        let oracleerr =
-            if errcode == OCI_ERROR {
-                by_handle.unwrap_or(
-                    OracleError { errcode, message: "Error with no details".to_string(), location }
-                )
-            } else if errcode == OCI_SUCCESS_WITH_INFO {
-                by_handle.unwrap_or(
-                    OracleError { errcode, message: "Success with info".to_string(), location }
-                )
-
-            } else {
-                let message =
-                    match errcode {
-                        OCI_NO_DATA => "No data",
-                        OCI_INVALID_HANDLE => "Invalid handle",
-                        OCI_NEED_DATA => "Need data",
-                        OCI_STILL_EXECUTING => "Steel executing",
-                        _ => panic!("Unknow return code")
-                    }.to_string();
-                OracleError { errcode, message, location }
-            };
-            Err(oracleerr)
-        }
+        return Ok(());
+    }
+
+    let records = handle
+        .map(diagnostic_chain)
+        .filter(|records| !records.is_empty())
+        .unwrap_or_else(|| {
+            let message = match errcode {
+                OCI_ERROR => "Error with no details",
+                OCI_SUCCESS_WITH_INFO => "Success with info",
+                OCI_NO_DATA => "No data",
+                OCI_INVALID_HANDLE => "Invalid handle",
+                OCI_NEED_DATA => "Need data",
+                OCI_STILL_EXECUTING => "Steel executing",
+                _ => panic!("Unknow return code")
+            }.to_string();
+            vec![Diagnostic { errcode, sqlstate: None, message }]
+        });
+
+    let sqlstate = records[0].sqlstate.clone();
+    Err(OracleError::Oracle { sqlstate, records, location })
 }
 
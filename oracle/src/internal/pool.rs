@@ -0,0 +1,93 @@
+use super::auth::ConnectParams;
+use super::connection::{Connection, Environment};
+use super::oci;
+
+/// A pool of reusable Oracle sessions backed by `OCISessionPoolCreate`.
+///
+/// Unlike `connect()`, which attaches a dedicated server+session handle set
+/// per `Connection`, a `ConnectionPool` keeps a range of sessions open
+/// against the shared `Environment` and hands them out on demand. Connections
+/// returned by `acquire()` release their session back to the pool on `Drop`
+/// instead of tearing it down.
+pub struct ConnectionPool {
+    env: &'static Environment,
+    poolhp: *mut oci::OCISPool,
+    errhp: *mut oci::OCIError,
+    pool_name: String,
+    authp: *mut oci::OCIAuthInfo,
+    mode: u32,
+}
+
+impl ConnectionPool {
+    /// Create a session pool against `db`, sized between `min` and `max`
+    /// sessions and growing by `increment` sessions at a time.
+    pub fn new(db: &str, username: &str, passwd: &str,
+               min: u32, max: u32, increment: u32) -> Result<ConnectionPool, oci::OracleError> {
+        Self::new_with_params(db, username, passwd, min, max, increment, &ConnectParams::default())
+    }
+
+    /// Create a session pool using a non-default session mode, credential
+    /// type, and/or proxy user (see `ConnectParams`) for every session drawn
+    /// from it via `acquire()` — e.g. `OCI_SYSDBA`, external authentication,
+    /// or proxy authentication.
+    pub fn new_with_params(db: &str, username: &str, passwd: &str,
+                            min: u32, max: u32, increment: u32,
+                            params: &ConnectParams) -> Result<ConnectionPool, oci::OracleError> {
+        let env = Environment::get()?;
+        let poolhp = oci::handle_alloc(env.envhp, oci::OCI_HTYPE_SPOOL)? as *mut oci::OCISPool;
+
+        let res = oci::session_pool_create(env.envhp, poolhp, env.errhp, db, username, passwd, min, max, increment);
+        let pool_name = match res {
+            Ok(name) => name,
+            Err(err) => {
+                oci::handle_free(poolhp as *mut oci::c_void, oci::OCI_HTYPE_SPOOL);
+                return Err(err);
+            }
+        };
+
+        let authp = match oci::prepare_auth_info(env.envhp, env.errhp, username, passwd,
+                                                  params.credential.as_oci(), params.proxy_user.as_deref()) {
+            Ok(authp) => authp,
+            Err(err) => {
+                oci::session_pool_destroy(poolhp, env.errhp);
+                oci::handle_free(poolhp as *mut oci::c_void, oci::OCI_HTYPE_SPOOL);
+                return Err(err);
+            }
+        };
+
+        Ok(ConnectionPool { env, poolhp, errhp: env.errhp, pool_name, authp, mode: params.mode.as_oci() })
+    }
+
+    /// Acquire a `Connection` from the pool. The returned `Connection`'s
+    /// `Drop` calls `OCISessionRelease` rather than `OCISessionEnd`/
+    /// `OCIServerDetach`.
+    pub fn acquire(&self) -> Result<Connection, oci::OracleError> {
+        // Each acquired connection gets its own OCI_HTYPE_ERROR handle: OCI
+        // error handles aren't safe for concurrent use from multiple
+        // threads, and concurrent `acquire()` callers (the whole point of
+        // pooling) would otherwise race on one handle shared by the pool.
+        let errhp = oci::handle_alloc(self.env.envhp, oci::OCI_HTYPE_ERROR)? as *mut oci::OCIError;
+        let svchp = match oci::session_get(self.env.envhp, errhp, &self.pool_name, self.authp, self.mode) {
+            Ok(svchp) => svchp,
+            Err(err) => {
+                oci::handle_free(errhp as *mut oci::c_void, oci::OCI_HTYPE_ERROR);
+                return Err(err);
+            }
+        };
+        Ok(Connection::new_pooled(self.env, errhp, svchp))
+    }
+}
+
+impl Drop for ConnectionPool {
+    fn drop(&mut self) {
+        oci::session_pool_destroy(self.poolhp, self.errhp);
+        oci::handle_free(self.poolhp as *mut oci::c_void, oci::OCI_HTYPE_SPOOL);
+        if !self.authp.is_null() {
+            oci::handle_free(self.authp as *mut oci::c_void, oci::OCI_HTYPE_AUTHINFO);
+        }
+    }
+}
+
+// for multithreading and lazy_static sharing, same rationale as `Environment`
+unsafe impl Sync for ConnectionPool {}
+unsafe impl Send for ConnectionPool {}
@@ -0,0 +1,44 @@
+//! Prepared statements handed out by `Connection::make_query`.
+
+use std::marker::PhantomData;
+
+use super::collection::BindArray;
+use super::connection::Connection;
+use super::oci;
+use super::values::{DescriptorsProvider, FromResultSet};
+
+/// A statement prepared against a `Connection`, parameterized by the row
+/// type `R` it will eventually fetch into.
+///
+/// Minimal stand-in: scalar bind parameters and fetching rows are out of
+/// scope for this change set, but `new` does the one thing `make_query`
+/// promises — go through the connection's statement cache instead of
+/// always issuing a fresh `OCIStmtPrepare2` — and `bind_array` lets a
+/// `collection::BindArray` be bound to a `:placeholder`. `new` checks the
+/// `OCIStmt` out of the cache so no other `Query` can alias it; `Drop`
+/// returns it.
+pub struct Query<'conn, R> {
+    conn: &'conn Connection,
+    sql: String,
+    stmtp: *mut oci::OCIStmt,
+    _row: PhantomData<R>,
+}
+
+impl<'conn, R: DescriptorsProvider + FromResultSet> Query<'conn, R> {
+    pub(crate) fn new(conn: &'conn Connection, sql: &str) -> Result<Query<'conn, R>, oci::OracleError> {
+        let stmtp = conn.prepare_cached(sql)?;
+        Ok(Query { conn, sql: sql.to_string(), stmtp, _row: PhantomData })
+    }
+
+    /// Bind `array` to `placeholder` (e.g. `":ids"`) so the query can read it
+    /// back as `... FROM TABLE(:ids)`.
+    pub fn bind_array(&self, placeholder: &str, array: &BindArray) -> Result<(), oci::OracleError> {
+        array.bind(self.stmtp, self.conn.errhp, placeholder)
+    }
+}
+
+impl<'conn, R> Drop for Query<'conn, R> {
+    fn drop(&mut self) {
+        self.conn.return_cached(&self.sql, self.stmtp);
+    }
+}
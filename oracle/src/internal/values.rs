@@ -0,0 +1,12 @@
+//! Traits bridging OCI define descriptors to typed result rows.
+//!
+//! These are minimal stand-ins: the full descriptor/result-set mapping is
+//! out of scope for the current change set, but `statement::Query` needs
+//! the bounds to exist for `Connection::make_query` to compile.
+
+/// Describes the OCI define descriptors a result row type needs bound
+/// before a fetch.
+pub trait DescriptorsProvider {}
+
+/// Builds a value of `Self` out of one fetched result-set row.
+pub trait FromResultSet {}
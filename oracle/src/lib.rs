@@ -4,3 +4,5 @@
 /// crates. This avoids elaborate import wrangling having to happen in every
 /// module.
 mod internal;
+
+pub use internal::*;